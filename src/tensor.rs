@@ -1,132 +1,149 @@
 //! An N-dimension tensor.
 
+pub mod autograd;
 pub mod error;
 pub mod layout;
+pub mod linalg;
+pub mod npy;
 
 use std::{
+    collections::HashMap,
     ops::{self, Index},
     rc::Rc,
 };
 
+use num_traits::Float;
 use rand::Rng;
 use rand_distr::Distribution;
 
 use self::{
     error::TensorError,
-    layout::{PositionIterator, TensorLayout},
+    layout::{PositionIterator, Layout},
 };
 
 /// An N-dimension array holding elements row-major order. Tensors are immutable and new ones are
 /// created each time we perform an operation. Tensors' underlying data is shared using reference
 /// counting and only cloned when an operations can't be performed without modifying the data.
+///
+/// `T` is bounded on [`num_traits::Float`] so that the same implementation serves `f32`, `f64`,
+/// and any other floating-point type, rather than hard-coding a single precision.
 #[derive(Debug)]
-pub struct Tensor {
-    data: Rc<Vec<f32>>,
-    layout: TensorLayout,
+pub struct Tensor<T> {
+    data: Rc<Vec<T>>,
+    layout: Layout,
 }
 
-impl ops::Add for &Tensor {
-    type Output = Result<Tensor, TensorError>;
+impl<T> Clone for Tensor<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            layout: self.layout.clone(),
+        }
+    }
+}
+
+impl<T: Float> ops::Add for &Tensor<T> {
+    type Output = Result<Tensor<T>, TensorError>;
 
-    fn add(self, rhs: &Tensor) -> Self::Output {
-        self.zip(rhs, |x, y| x + y)
+    fn add(self, rhs: &Tensor<T>) -> Self::Output {
+        self.zip(rhs, |x, y| *x + *y)
     }
 }
 
-impl ops::Add<Tensor> for &Tensor {
-    type Output = Result<Tensor, TensorError>;
+impl<T: Float> ops::Add<Tensor<T>> for &Tensor<T> {
+    type Output = Result<Tensor<T>, TensorError>;
 
-    fn add(self, rhs: Tensor) -> Self::Output {
+    fn add(self, rhs: Tensor<T>) -> Self::Output {
         self + &rhs
     }
 }
 
-impl ops::Add<Result<Tensor, TensorError>> for &Tensor {
-    type Output = Result<Tensor, TensorError>;
+impl<T: Float> ops::Add<Result<Tensor<T>, TensorError>> for &Tensor<T> {
+    type Output = Result<Tensor<T>, TensorError>;
 
-    fn add(self, rhs: Result<Tensor, TensorError>) -> Self::Output {
+    fn add(self, rhs: Result<Tensor<T>, TensorError>) -> Self::Output {
         rhs.and_then(|rhs| self + &rhs)
     }
 }
 
-impl ops::Add<&Tensor> for Result<Tensor, TensorError> {
-    type Output = Result<Tensor, TensorError>;
+impl<T: Float> ops::Add<&Tensor<T>> for Result<Tensor<T>, TensorError> {
+    type Output = Result<Tensor<T>, TensorError>;
 
-    fn add(self, rhs: &Tensor) -> Self::Output {
+    fn add(self, rhs: &Tensor<T>) -> Self::Output {
         self.and_then(|lhs| &lhs + rhs)
     }
 }
 
-impl ops::Mul for &Tensor {
-    type Output = Result<Tensor, TensorError>;
+impl<T: Float> ops::Mul for &Tensor<T> {
+    type Output = Result<Tensor<T>, TensorError>;
 
-    fn mul(self, rhs: &Tensor) -> Self::Output {
-        self.zip(rhs, |x, y| x * y)
+    fn mul(self, rhs: &Tensor<T>) -> Self::Output {
+        self.zip(rhs, |x, y| *x * *y)
     }
 }
 
-impl ops::Mul<Tensor> for &Tensor {
-    type Output = Result<Tensor, TensorError>;
+impl<T: Float> ops::Mul<Tensor<T>> for &Tensor<T> {
+    type Output = Result<Tensor<T>, TensorError>;
 
-    fn mul(self, rhs: Tensor) -> Self::Output {
+    fn mul(self, rhs: Tensor<T>) -> Self::Output {
         self * &rhs
     }
 }
 
-impl ops::Mul<Result<Tensor, TensorError>> for &Tensor {
-    type Output = Result<Tensor, TensorError>;
+impl<T: Float> ops::Mul<Result<Tensor<T>, TensorError>> for &Tensor<T> {
+    type Output = Result<Tensor<T>, TensorError>;
 
-    fn mul(self, rhs: Result<Tensor, TensorError>) -> Self::Output {
+    fn mul(self, rhs: Result<Tensor<T>, TensorError>) -> Self::Output {
         rhs.and_then(|rhs| self * &rhs)
     }
 }
 
-impl ops::Mul<&Tensor> for Result<Tensor, TensorError> {
-    type Output = Result<Tensor, TensorError>;
+impl<T: Float> ops::Mul<&Tensor<T>> for Result<Tensor<T>, TensorError> {
+    type Output = Result<Tensor<T>, TensorError>;
 
-    fn mul(self, rhs: &Tensor) -> Self::Output {
-        self.and_then(|lhs| lhs.zip(rhs, |x, y| x * y))
+    fn mul(self, rhs: &Tensor<T>) -> Self::Output {
+        self.and_then(|lhs| lhs.zip(rhs, |x, y| *x * *y))
     }
 }
 
-impl From<Vec<f32>> for Tensor {
-    fn from(data: Vec<f32>) -> Self {
+impl<T> From<Vec<T>> for Tensor<T> {
+    fn from(data: Vec<T>) -> Self {
         let data_len = data.len();
         Self {
             data: Rc::new(data),
-            layout: TensorLayout::from(&[data_len]),
+            layout: Layout::from(&[data_len]),
         }
     }
 }
 
-impl<const N: usize> From<[f32; N]> for Tensor {
-    fn from(data: [f32; N]) -> Self {
+impl<T: Clone, const N: usize> From<[T; N]> for Tensor<T> {
+    fn from(data: [T; N]) -> Self {
         Tensor::from(data.to_vec())
     }
 }
 
-impl<const N: usize> From<&[f32; N]> for Tensor {
-    fn from(data: &[f32; N]) -> Self {
+impl<T: Clone, const N: usize> From<&[T; N]> for Tensor<T> {
+    fn from(data: &[T; N]) -> Self {
         Tensor::from(data.to_vec())
     }
 }
 
-impl From<&[f32]> for Tensor {
-    fn from(data: &[f32]) -> Self {
+impl<T: Clone> From<&[T]> for Tensor<T> {
+    fn from(data: &[T]) -> Self {
         Tensor::from(data.to_vec())
     }
 }
 
-impl Index<usize> for &Tensor {
-    type Output = f32;
+impl<T> Index<usize> for &Tensor<T> {
+    type Output = T;
 
     fn index(&self, pos: usize) -> &Self::Output {
         &self.data[pos]
     }
 }
 
-impl Index<&[usize]> for &Tensor {
-    type Output = f32;
+impl<T> Index<&[usize]> for &Tensor<T> {
+    type Output = T;
 
     fn index(&self, index: &[usize]) -> &Self::Output {
         let pos = self.layout.index_to_position(index);
@@ -134,34 +151,34 @@ impl Index<&[usize]> for &Tensor {
     }
 }
 
-impl Index<Vec<usize>> for &Tensor {
-    type Output = f32;
+impl<T> Index<Vec<usize>> for &Tensor<T> {
+    type Output = T;
 
     fn index(&self, index: Vec<usize>) -> &Self::Output {
         &self[index.as_slice()]
     }
 }
 
-impl<const N: usize> Index<[usize; N]> for &Tensor {
-    type Output = f32;
+impl<T, const N: usize> Index<[usize; N]> for &Tensor<T> {
+    type Output = T;
 
     fn index(&self, index: [usize; N]) -> &Self::Output {
         &self[&index]
     }
 }
 
-impl<const N: usize> Index<&[usize; N]> for &Tensor {
-    type Output = f32;
+impl<T, const N: usize> Index<&[usize; N]> for &Tensor<T> {
+    type Output = T;
 
     fn index(&self, index: &[usize; N]) -> &Self::Output {
         &self[index.as_slice()]
     }
 }
 
-impl<'a> IntoIterator for &'a Tensor {
-    type Item = &'a f32;
+impl<'a, T> IntoIterator for &'a Tensor<T> {
+    type Item = &'a T;
 
-    type IntoIter = TensorRowIter<'a>;
+    type IntoIter = TensorRowIter<'a, T>;
 
     fn into_iter(self) -> Self::IntoIter {
         Self::IntoIter {
@@ -171,18 +188,18 @@ impl<'a> IntoIterator for &'a Tensor {
     }
 }
 
-impl Tensor {
+impl<T: Float> Tensor<T> {
     /// Creates a new tensor holding a scalar.
-    pub fn scalar(x: f32) -> Self {
+    pub fn scalar(x: T) -> Self {
         Self {
             data: Rc::new(vec![x]),
-            layout: TensorLayout::scalar(),
+            layout: Layout::scalar(),
         }
     }
 
     /// Creates a new tensor using the given data and layout.
-    pub fn shaped(shape: &[usize], data: &[f32]) -> Result<Self, TensorError> {
-        let layout = TensorLayout::from(shape);
+    pub fn shaped(shape: &[usize], data: &[T]) -> Result<Self, TensorError> {
+        let layout = Layout::from(shape);
         if layout.elems() != data.len() {
             return Err(TensorError::IncompatibleShapes(
                 shape.to_vec(),
@@ -199,9 +216,9 @@ impl Tensor {
     pub fn rand<R, D>(rng: R, distribution: D, shape: &[usize]) -> Self
     where
         R: Rng,
-        D: Distribution<f32>,
+        D: Distribution<T>,
     {
-        let layout = TensorLayout::from(shape);
+        let layout = Layout::from(shape);
         let data = rng.sample_iter(distribution).take(layout.elems()).collect();
         Self {
             data: Rc::new(data),
@@ -210,7 +227,7 @@ impl Tensor {
     }
 
     /// Returns the layout of this tensor.
-    pub fn layout(&self) -> &TensorLayout {
+    pub fn layout(&self) -> &Layout {
         &self.layout
     }
 
@@ -274,20 +291,174 @@ impl Tensor {
         sumprod.reshape(&shape)
     }
 
-    /// Returns a new tensor reduced along the given dimensions by summing all elements.
-    pub fn sum(&self, dims: &[usize]) -> Result<Tensor, TensorError> {
-        self.reduce(dims, 0.0, |x, y| x + y)
+    /// Einstein-summation contraction, e.g. `"ij,jk->ik"` for matmul, `"ii->i"` for the diagonal,
+    /// `"ij->ji"` for transpose, or `"ijk->ik"` for a reduction. Labels that appear in the inputs
+    /// but not in the output are contracted (summed); the rest are free and determine the output
+    /// shape. A label shared by several operands must have the same extent everywhere, otherwise
+    /// this returns [`TensorError::IncompatibleShapes`].
+    ///
+    /// This is implemented by iterating over the full cartesian product of every distinct label's
+    /// values, gathering the corresponding scalar from each operand, and accumulating the product
+    /// into the output position determined by the free labels — a single engine that generalizes
+    /// `matmul`, `sum`, and `transpose`.
+    pub fn einsum(spec: &str, operands: &[&Tensor<T>]) -> Result<Tensor<T>, TensorError> {
+        let (input_labels, output_labels) = parse_einsum_spec(spec, operands.len())?;
+
+        let mut extents: HashMap<char, usize> = HashMap::new();
+        for (labels, operand) in input_labels.iter().zip(operands) {
+            let shape = operand.layout.shape();
+            if labels.len() != shape.len() {
+                return Err(TensorError::IncompatibleShapes(
+                    labels.iter().map(|_| 0).collect(),
+                    shape.to_vec(),
+                ));
+            }
+            for (&label, &extent) in labels.iter().zip(shape) {
+                match extents.get(&label) {
+                    Some(&existing) if existing != extent => {
+                        return Err(TensorError::IncompatibleShapes(vec![existing], vec![extent]));
+                    }
+                    _ => {
+                        extents.insert(label, extent);
+                    }
+                }
+            }
+        }
+
+        let mut all_labels: Vec<char> = Vec::new();
+        for labels in &input_labels {
+            for &label in labels {
+                if !all_labels.contains(&label) {
+                    all_labels.push(label);
+                }
+            }
+        }
+
+        if let Some(&bad) = output_labels.iter().find(|label| !extents.contains_key(label)) {
+            return Err(TensorError::InvalidEinsumSpec(format!(
+                "output label '{bad}' in {spec:?} doesn't appear in any operand"
+            )));
+        }
+
+        let out_shape: Vec<usize> = output_labels
+            .iter()
+            .map(|label| extents[label])
+            .collect();
+        let out_layout = Layout::from(&out_shape);
+        let mut out_data = vec![T::zero(); out_layout.elems()];
+
+        let dims: Vec<usize> = all_labels.iter().map(|label| extents[label]).collect();
+        for assignment in cartesian_product(&dims) {
+            let value_of: HashMap<char, usize> = all_labels
+                .iter()
+                .copied()
+                .zip(assignment.iter().copied())
+                .collect();
+
+            let mut product = T::one();
+            for (labels, operand) in input_labels.iter().zip(operands) {
+                let idx: Vec<usize> = labels.iter().map(|label| value_of[label]).collect();
+                let pos = operand.layout.index_to_position(&idx);
+                product = product * operand.data[pos];
+            }
+
+            let out_idx: Vec<usize> = output_labels.iter().map(|label| value_of[label]).collect();
+            let out_pos = out_layout.index_to_position(&out_idx);
+            out_data[out_pos] = out_data[out_pos] + product;
+        }
+
+        Tensor::shaped(&out_shape, &out_data)
+    }
+
+    /// Batched 2-D cross-correlation (the convolution used by neural networks).
+    ///
+    /// `self` has layout `(N, C_in, H, W)`, `kernel` has layout `(C_out, C_in, KH, KW)`, and the
+    /// result has layout `(N, C_out, H_out, W_out)` with
+    /// `H_out = (H + 2*pad_h - KH) / stride_h + 1` (and likewise for the width). Out-of-bounds
+    /// reads introduced by `padding` are treated as zero.
+    pub fn conv2d(
+        &self,
+        kernel: &Tensor<T>,
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Result<Tensor<T>, TensorError> {
+        let input_shape = self.layout.shape();
+        let kernel_shape = kernel.layout.shape();
+        if input_shape.len() != 4 || kernel_shape.len() != 4 || input_shape[1] != kernel_shape[1] {
+            return Err(TensorError::IncompatibleShapes(
+                input_shape.to_vec(),
+                kernel_shape.to_vec(),
+            ));
+        }
+        let (n, c_in, h, w) = (input_shape[0], input_shape[1], input_shape[2], input_shape[3]);
+        let (c_out, kh, kw) = (kernel_shape[0], kernel_shape[2], kernel_shape[3]);
+        let (stride_h, stride_w) = stride;
+        let (pad_h, pad_w) = padding;
+        let padded_h = h + 2 * pad_h;
+        let padded_w = w + 2 * pad_w;
+        if stride_h == 0 || stride_w == 0 || padded_h < kh || padded_w < kw {
+            return Err(TensorError::IncompatibleShapes(
+                input_shape.to_vec(),
+                kernel_shape.to_vec(),
+            ));
+        }
+        let h_out = (padded_h - kh) / stride_h + 1;
+        let w_out = (padded_w - kw) / stride_w + 1;
+
+        let out_shape = vec![n, c_out, h_out, w_out];
+        let out_layout = Layout::from(&out_shape);
+        let mut out_data = vec![T::zero(); out_layout.elems()];
+
+        for ni in 0..n {
+            for co in 0..c_out {
+                for oh in 0..h_out {
+                    for ow in 0..w_out {
+                        let mut acc = T::zero();
+                        for ci in 0..c_in {
+                            for khi in 0..kh {
+                                let ih = oh * stride_h + khi;
+                                if ih < pad_h || ih - pad_h >= h {
+                                    continue;
+                                }
+                                let ih = ih - pad_h;
+                                for kwi in 0..kw {
+                                    let iw = ow * stride_w + kwi;
+                                    if iw < pad_w || iw - pad_w >= w {
+                                        continue;
+                                    }
+                                    let iw = iw - pad_w;
+                                    let in_pos = self.layout.index_to_position(&[ni, ci, ih, iw]);
+                                    let k_pos = kernel.layout.index_to_position(&[co, ci, khi, kwi]);
+                                    acc = acc + self.data[in_pos] * kernel.data[k_pos];
+                                }
+                            }
+                        }
+                        let out_pos = out_layout.index_to_position(&[ni, co, oh, ow]);
+                        out_data[out_pos] = acc;
+                    }
+                }
+            }
+        }
+
+        Tensor::shaped(&out_shape, &out_data)
+    }
+
+    /// Returns a new tensor reduced along the given dimensions by summing all elements, keeping
+    /// the reduced dimensions at size 1 (see [`Tensor::reduce`]).
+    pub fn sum(&self, dims: &[usize]) -> Result<Tensor<T>, TensorError> {
+        self.reduce(dims, T::zero(), |x, y| *x + *y)
     }
 
-    /// Returns a new tensor reduced along the given dimensions by multiplying all elements.
-    pub fn product(&self, dims: &[usize]) -> Result<Tensor, TensorError> {
-        self.reduce(dims, 1.0, |x, y| x * y)
+    /// Returns a new tensor reduced along the given dimensions by multiplying all elements,
+    /// keeping the reduced dimensions at size 1 (see [`Tensor::reduce`]).
+    pub fn product(&self, dims: &[usize]) -> Result<Tensor<T>, TensorError> {
+        self.reduce(dims, T::one(), |x, y| *x * *y)
     }
 
     /// Applies the unary function `op` to all elements in the tensor.
     pub fn map<F>(&self, op: F) -> Self
     where
-        F: Fn(&f32) -> f32,
+        F: Fn(&T) -> T,
     {
         let mut res = Vec::with_capacity(self.layout.elems());
         for x in self.into_iter() {
@@ -295,7 +466,7 @@ impl Tensor {
         }
         Self {
             data: Rc::new(res),
-            layout: TensorLayout::from(self.layout.shape()),
+            layout: Layout::from(self.layout.shape()),
         }
     }
 
@@ -305,7 +476,7 @@ impl Tensor {
     /// [NumPy's broadcasting]: https://numpy.org/doc/stable/user/basics.broadcasting.html
     pub fn zip<F>(&self, other: &Self, op: F) -> Result<Self, TensorError>
     where
-        F: Fn(&f32, &f32) -> f32,
+        F: Fn(&T, &T) -> T,
     {
         let (lhs, rhs) = self.broadcast(other)?;
         let mut res = Vec::with_capacity(lhs.layout.elems());
@@ -314,18 +485,54 @@ impl Tensor {
         }
         Ok(Self {
             data: Rc::new(res),
-            layout: TensorLayout::from(lhs.layout.shape()),
+            layout: Layout::from(lhs.layout.shape()),
+        })
+    }
+
+    /// Returns whether every element of `self` and `other` (after broadcasting) is within
+    /// tolerance: `|a - b| <= atol + rtol * |b|`. See [`Tensor::allclose_default`] for a version
+    /// using default tolerances.
+    pub fn allclose(&self, other: &Self, rtol: T, atol: T) -> Result<bool, TensorError> {
+        let (lhs, rhs) = self.broadcast(other)?;
+        Ok(lhs
+            .into_iter()
+            .zip(rhs.into_iter())
+            .all(|(a, b)| (*a - *b).abs() <= atol + rtol * b.abs()))
+    }
+
+    /// [`Tensor::allclose`] with default tolerances `rtol = 1e-4`, `atol = 1e-5` (looser than
+    /// NumPy's own defaults).
+    pub fn allclose_default(&self, other: &Self) -> Result<bool, TensorError> {
+        self.allclose(
+            other,
+            T::from(1e-4).expect("1e-4 fits in T"),
+            T::from(1e-5).expect("1e-5 fits in T"),
+        )
+    }
+
+    /// Elementwise version of [`Tensor::allclose`] (using the same default tolerances as
+    /// [`Tensor::allclose_default`]), returning a 0/1 mask tensor instead of a single bool.
+    pub fn is_close(&self, other: &Self) -> Result<Tensor<T>, TensorError> {
+        let rtol = T::from(1e-4).expect("1e-4 fits in T");
+        let atol = T::from(1e-5).expect("1e-5 fits in T");
+        self.zip(other, move |a, b| {
+            if (*a - *b).abs() <= atol + rtol * b.abs() {
+                T::one()
+            } else {
+                T::zero()
+            }
         })
     }
 
     /// Reduces all elements along the given dimensions into a single element using the given
-    /// operation. This effectively reduces the rank of the tensor by the number of input
-    /// dimensions. See [NumPy's reduce] for more information.
+    /// operation. Keeps the rank unchanged: each dimension in `dims` becomes size 1 rather than
+    /// being dropped (`matmul` and `autograd`'s `unbroadcast` both rely on this to know which axes
+    /// were reduced). See [NumPy's reduce] for more information.
     ///
     /// [NumPy's reduce]: https://numpy.org/doc/stable/reference/generated/numpy.ufunc.reduce.html#numpy-ufunc-reduce
-    pub fn reduce<F>(&self, dims: &[usize], default: f32, op: F) -> Result<Self, TensorError>
+    pub fn reduce<F>(&self, dims: &[usize], default: T, op: F) -> Result<Self, TensorError>
     where
-        F: Fn(&f32, &f32) -> f32,
+        F: Fn(&T, &T) -> T,
     {
         let (layout, reducer) = self.layout.reduce(dims)?;
         let mut res = vec![default; layout.elems()];
@@ -367,19 +574,60 @@ impl Tensor {
         })
     }
 
+    /// Returns a view onto a contiguous range of length `len` along `dim`, starting at `start`.
+    /// Shares the underlying data with `self`; no elements are copied.
+    pub fn narrow(&self, dim: usize, start: usize, len: usize) -> Result<Self, TensorError> {
+        let layout = self.layout.narrow(dim, start, len)?;
+        Ok(Self {
+            data: self.data.clone(),
+            layout,
+        })
+    }
+
+    /// Gathers the entries at `indices` along `dim` into a new tensor, à la ndarray's `select`.
+    /// Unlike [`Tensor::narrow`] this always materializes new data, since the picked indices
+    /// aren't necessarily contiguous.
+    pub fn select(&self, dim: usize, indices: &[usize]) -> Result<Self, TensorError> {
+        let shape = self.layout.shape();
+        if dim >= shape.len() {
+            return Err(TensorError::IncompatibleShapes(shape.to_vec(), vec![dim]));
+        }
+        if let Some(&bad) = indices.iter().find(|&&i| i >= shape[dim]) {
+            return Err(TensorError::IncompatibleShapes(shape.to_vec(), vec![bad]));
+        }
+
+        let mut out_shape = shape.to_vec();
+        out_shape[dim] = indices.len();
+        let out_layout = Layout::from(&out_shape);
+        let mut out_data = vec![T::zero(); out_layout.elems()];
+        for out_idx in out_layout.iter_index() {
+            let mut src_idx = out_idx.clone();
+            src_idx[dim] = indices[out_idx[dim]];
+            let src_pos = self.layout.index_to_position(&src_idx);
+            let out_pos = out_layout.index_to_position(&out_idx);
+            out_data[out_pos] = self.data[src_pos];
+        }
+        Tensor::shaped(&out_shape, &out_data)
+    }
+
     /// Reshapes the tensor to the given shape. This might clone the data if the new shape can't be
-    /// represented contiguously basing on the current layout.
+    /// represented contiguously basing on the current layout, in which case the tensor is first
+    /// materialized into row-major order (following `self`'s current logical iteration order, not
+    /// its raw buffer, so a transposed/permuted/narrowed view reshapes correctly).
     pub fn reshape(&self, shape: &[usize]) -> Result<Self, TensorError> {
         match self.layout.reshape(shape)? {
             Some(layout) => Ok(Self {
                 data: self.data.clone(),
                 layout,
             }),
-            None => Self::from(self.data.as_ref().clone()).reshape(shape),
+            None => {
+                let contiguous: Vec<T> = self.into_iter().copied().collect();
+                Tensor::from(contiguous).reshape(shape)
+            }
         }
     }
 
-    /// Broadcast the tensors and returns their broadcasted versions. See [TensorLayout::broadcast]
+    /// Broadcast the tensors and returns their broadcasted versions. See [Layout::broadcast]
     /// for more details.
     fn broadcast(&self, other: &Self) -> Result<(Self, Self), TensorError> {
         let (lhs_layout, rhs_layout) = self.layout.broadcast(&other.layout)?;
@@ -395,14 +643,46 @@ impl Tensor {
     }
 }
 
+/// Parses an `einsum` spec such as `"ij,jk->ik"` into the per-operand index label lists and the
+/// output label list, validating that the number of comma-separated operand specs matches
+/// `n_operands`.
+fn parse_einsum_spec(spec: &str, n_operands: usize) -> Result<(Vec<Vec<char>>, Vec<char>), TensorError> {
+    let (inputs, output) = spec
+        .split_once("->")
+        .ok_or_else(|| TensorError::InvalidEinsumSpec(spec.to_string()))?;
+    let input_labels: Vec<Vec<char>> = inputs.split(',').map(|s| s.chars().collect()).collect();
+    if input_labels.len() != n_operands {
+        return Err(TensorError::InvalidEinsumSpec(spec.to_string()));
+    }
+    Ok((input_labels, output.chars().collect()))
+}
+
+/// Returns every combination of indices `(i_0, ..., i_n)` with `0 <= i_k < dims[k]`, in row-major
+/// order over `dims`.
+fn cartesian_product(dims: &[usize]) -> Vec<Vec<usize>> {
+    let mut result = vec![Vec::new()];
+    for &dim in dims {
+        let mut next = Vec::with_capacity(result.len() * dim);
+        for combo in &result {
+            for value in 0..dim {
+                let mut next_combo = combo.clone();
+                next_combo.push(value);
+                next.push(next_combo);
+            }
+        }
+        result = next;
+    }
+    result
+}
+
 /// A row-major iterator over a tensor.
-pub struct TensorRowIter<'a> {
-    tensor: &'a Tensor,
+pub struct TensorRowIter<'a, T> {
+    tensor: &'a Tensor<T>,
     position_iterator: PositionIterator<'a>,
 }
 
-impl<'a> Iterator for TensorRowIter<'a> {
-    type Item = &'a f32;
+impl<'a, T> Iterator for TensorRowIter<'a, T> {
+    type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.position_iterator
@@ -0,0 +1,266 @@
+//! Reverse-mode automatic differentiation built on top of [`Tensor`].
+//!
+//! A [`Var`] wraps a [`Tensor`] together with a gradient accumulator and, when it was produced by
+//! a differentiable operation, a node on the computation tape describing how to propagate a
+//! gradient back to its parents. Calling [`Var::backward`] on a scalar walks the tape in reverse
+//! topological order, invoking each node's backward closure and accumulating the results into the
+//! parent `Var`s via the chain rule.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use num_traits::Float;
+
+use super::error::TensorError;
+use super::Tensor;
+
+/// A backward closure computing the local vector-Jacobian product for a single op, i.e. given the
+/// gradient flowing into the op's output, it returns the gradient for each of the op's parents.
+type Backward<T> = Box<dyn Fn(&Tensor<T>) -> Result<Vec<Tensor<T>>, TensorError>>;
+
+/// A node on the computation tape recording the parents of a [`Var`] and how to back-propagate
+/// through the operation that produced it.
+struct Node<T> {
+    parents: Vec<Var<T>>,
+    backward: Backward<T>,
+}
+
+/// A tensor participating in reverse-mode automatic differentiation.
+///
+/// Cloning a `Var` is cheap: the underlying value, gradient and tape node are all shared via
+/// reference counting, mirroring how [`Tensor`] shares its data.
+#[derive(Clone)]
+pub struct Var<T> {
+    value: Tensor<T>,
+    grad: Rc<RefCell<Option<Tensor<T>>>>,
+    node: Option<Rc<Node<T>>>,
+}
+
+impl<T: Float + 'static> Var<T> {
+    /// Wraps a tensor as a leaf of the computation graph, i.e. one with no parents to propagate
+    /// gradients to.
+    pub fn new(value: Tensor<T>) -> Self {
+        Self {
+            value,
+            grad: Rc::new(RefCell::new(None)),
+            node: None,
+        }
+    }
+
+    fn from_op(
+        value: Tensor<T>,
+        parents: Vec<Var<T>>,
+        backward: impl Fn(&Tensor<T>) -> Result<Vec<Tensor<T>>, TensorError> + 'static,
+    ) -> Self {
+        Self {
+            value,
+            grad: Rc::new(RefCell::new(None)),
+            node: Some(Rc::new(Node {
+                parents,
+                backward: Box::new(backward),
+            })),
+        }
+    }
+
+    /// Returns the forward value held by this `Var`.
+    pub fn value(&self) -> &Tensor<T> {
+        &self.value
+    }
+
+    /// Returns the gradient accumulated by calls to [`Var::backward`], if any. Gradients are never
+    /// reset, so repeated calls add up.
+    pub fn grad(&self) -> Option<Tensor<T>> {
+        self.grad.borrow().clone()
+    }
+
+    fn accumulate(&self, g: Tensor<T>) -> Result<(), TensorError> {
+        let mut slot = self.grad.borrow_mut();
+        let next = match slot.take() {
+            Some(existing) => (&existing + &g)?,
+            None => g,
+        };
+        *slot = Some(next);
+        Ok(())
+    }
+
+    /// Runs backward-mode differentiation starting from this `Var`, which must hold a scalar.
+    /// Seeds its gradient with ones, walks the tape in reverse topological order, and accumulates
+    /// each parent's gradient via the chain rule.
+    pub fn backward(&self) -> Result<(), TensorError> {
+        if self.value.layout().elems() != 1 {
+            return Err(TensorError::IncompatibleShapes(
+                self.value.layout().shape().to_vec(),
+                vec![1],
+            ));
+        }
+
+        let seed = Tensor::shaped(self.value.layout().shape(), &[T::one()])?;
+        self.accumulate(seed)?;
+
+        let mut visited = HashSet::new();
+        let mut order = Vec::new();
+        topo_sort(self, &mut visited, &mut order);
+
+        for var in order.into_iter().rev() {
+            let Some(node) = &var.node else {
+                continue;
+            };
+            let grad = var
+                .grad()
+                .expect("a node reached during the backward walk must have a seeded gradient");
+            let parent_grads = (node.backward)(&grad)?;
+            for (parent, parent_grad) in node.parents.iter().zip(parent_grads) {
+                parent.accumulate(parent_grad)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Elementwise addition, broadcasting as [`Tensor::zip`] does.
+    pub fn add(&self, other: &Self) -> Result<Self, TensorError> {
+        let value = (&self.value + &other.value)?;
+        let lhs_shape = self.value.layout().shape().to_vec();
+        let rhs_shape = other.value.layout().shape().to_vec();
+        Ok(Self::from_op(
+            value,
+            vec![self.clone(), other.clone()],
+            move |g| Ok(vec![unbroadcast(g, &lhs_shape)?, unbroadcast(g, &rhs_shape)?]),
+        ))
+    }
+
+    /// Elementwise multiplication, broadcasting as [`Tensor::zip`] does.
+    pub fn mul(&self, other: &Self) -> Result<Self, TensorError> {
+        let value = (&self.value * &other.value)?;
+        let lhs = self.clone();
+        let rhs = other.clone();
+        let lhs_shape = self.value.layout().shape().to_vec();
+        let rhs_shape = other.value.layout().shape().to_vec();
+        Ok(Self::from_op(value, vec![lhs.clone(), rhs.clone()], move |g| {
+            let d_lhs = (g * &rhs.value)?;
+            let d_rhs = (g * &lhs.value)?;
+            Ok(vec![unbroadcast(&d_lhs, &lhs_shape)?, unbroadcast(&d_rhs, &rhs_shape)?])
+        }))
+    }
+
+    /// Matrix product, see [`Tensor::matmul`]. `dA = dC·Bᵀ` and `dB = Aᵀ·dC`, un-broadcast back down
+    /// to the parents' original shapes since `matmul` broadcasts batch dimensions the same way
+    /// `zip` does.
+    pub fn matmul(&self, other: &Self) -> Result<Self, TensorError> {
+        let value = self.value.matmul(&other.value)?;
+        let lhs = self.clone();
+        let rhs = other.clone();
+        let lhs_shape = self.value.layout().shape().to_vec();
+        let rhs_shape = other.value.layout().shape().to_vec();
+        Ok(Self::from_op(value, vec![lhs.clone(), rhs.clone()], move |g| {
+            let rhs_t = last_two_transposed(&rhs.value)?;
+            let lhs_t = last_two_transposed(&lhs.value)?;
+            let d_lhs = g.matmul(&rhs_t)?;
+            let d_rhs = lhs_t.matmul(g)?;
+            Ok(vec![unbroadcast(&d_lhs, &lhs_shape)?, unbroadcast(&d_rhs, &rhs_shape)?])
+        }))
+    }
+
+    /// Sums along `dims`, see [`Tensor::sum`]. The incoming gradient is broadcast back up to the
+    /// pre-reduction shape.
+    pub fn sum(&self, dims: &[usize]) -> Result<Self, TensorError> {
+        let value = self.value.sum(dims)?;
+        let input = self.clone();
+        let orig_shape = self.value.layout().shape().to_vec();
+        Ok(Self::from_op(value, vec![input.clone()], move |g| {
+            Ok(vec![broadcast_to(g, &orig_shape)?])
+        }))
+    }
+
+    /// Swaps two dimensions, see [`Tensor::transpose`]. Backward simply transposes the same two
+    /// dimensions back.
+    pub fn transpose(&self, dim0: usize, dim1: usize) -> Result<Self, TensorError> {
+        let value = self.value.transpose(dim0, dim1)?;
+        let input = self.clone();
+        Ok(Self::from_op(value, vec![input.clone()], move |g| {
+            Ok(vec![g.transpose(dim0, dim1)?])
+        }))
+    }
+
+    /// Reshapes to `shape`, see [`Tensor::reshape`]. Backward reshapes the gradient back to the
+    /// input's original shape.
+    pub fn reshape(&self, shape: &[usize]) -> Result<Self, TensorError> {
+        let value = self.value.reshape(shape)?;
+        let input = self.clone();
+        let orig_shape = self.value.layout().shape().to_vec();
+        Ok(Self::from_op(value, vec![input.clone()], move |g| {
+            Ok(vec![g.reshape(&orig_shape)?])
+        }))
+    }
+}
+
+/// Visits parents before the node itself, producing a topological order (parents precede
+/// children). `visited` is keyed by the address of each `Var`'s gradient cell, which is unique per
+/// `Var` even though `Var` itself doesn't implement `Eq`.
+fn topo_sort<T: Float + 'static>(
+    var: &Var<T>,
+    visited: &mut HashSet<usize>,
+    order: &mut Vec<Var<T>>,
+) {
+    let id = Rc::as_ptr(&var.grad) as usize;
+    if !visited.insert(id) {
+        return;
+    }
+    if let Some(node) = &var.node {
+        for parent in &node.parents {
+            topo_sort(parent, visited, order);
+        }
+    }
+    order.push(var.clone());
+}
+
+/// Transposes the last two dimensions of a (possibly batched) matrix, as used by `matmul`'s
+/// backward pass to turn `B` into `Bᵀ` and `A` into `Aᵀ`.
+fn last_two_transposed<T: Float>(t: &Tensor<T>) -> Result<Tensor<T>, TensorError> {
+    let rank = t.layout().shape().len();
+    t.transpose(rank - 1, rank - 2)
+}
+
+/// Broadcasts `value` up to `shape` by adding it to a zero tensor of that shape, reusing
+/// [`Tensor::zip`]'s broadcasting rules.
+fn broadcast_to<T: Float>(value: &Tensor<T>, shape: &[usize]) -> Result<Tensor<T>, TensorError> {
+    let zeros = Tensor::shaped(shape, &vec![T::zero(); shape.iter().product()])?;
+    &zeros + value
+}
+
+/// Un-does a [`Tensor::broadcast`] by summing a gradient back down to `shape`: dimensions that
+/// were expanded from size 1 (stride 0) are summed out, restoring the pre-broadcast shape.
+///
+/// Relies on [`Tensor::sum`] keeping reduced dimensions at size 1 (keepdims): after
+/// `grad.sum(&leading)`, `reduced`'s shape still has the same rank as `grad_shape`, so slicing off
+/// its first `rank_diff` entries (all 1s from the leading sum) — not a second rank reduction —
+/// yields exactly `shape`'s rank.
+fn unbroadcast<T: Float>(grad: &Tensor<T>, shape: &[usize]) -> Result<Tensor<T>, TensorError> {
+    let grad_shape = grad.layout().shape().to_vec();
+    if grad_shape == shape {
+        return Ok(grad.map(|x| *x));
+    }
+
+    let rank_diff = grad_shape.len() - shape.len();
+    let leading: Vec<usize> = (0..rank_diff).collect();
+    let reduced = if leading.is_empty() {
+        grad.map(|x| *x)
+    } else {
+        grad.sum(&leading)?
+    };
+    let trimmed_shape = &reduced.layout().shape()[rank_diff..];
+    let reduced = reduced.reshape(trimmed_shape)?;
+
+    let expanded_dims: Vec<usize> = shape
+        .iter()
+        .enumerate()
+        .filter(|(i, &s)| s == 1 && trimmed_shape[*i] != 1)
+        .map(|(i, _)| i)
+        .collect();
+    let reduced = if expanded_dims.is_empty() {
+        reduced
+    } else {
+        reduced.sum(&expanded_dims)?
+    };
+    reduced.reshape(shape)
+}
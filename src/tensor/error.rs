@@ -0,0 +1,35 @@
+//! Errors returned by fallible [`Tensor`](super::Tensor) operations.
+
+use std::fmt;
+
+/// The error type returned by fallible tensor operations.
+#[derive(Debug)]
+pub enum TensorError {
+    /// Two shapes (or shape-like values, e.g. a dimension/permutation) couldn't be reconciled by
+    /// the operation that reported them.
+    IncompatibleShapes(Vec<usize>, Vec<usize>),
+    /// An `einsum` spec was malformed, e.g. missing `"->"` or the wrong number of operand specs.
+    InvalidEinsumSpec(String),
+    /// A square matrix had no inverse (a near-zero pivot was found during LU decomposition).
+    SingularMatrix,
+    /// A `.npy`/`.npz` file was malformed or didn't match the tensor type being loaded into.
+    InvalidNpyFile(String),
+    /// An I/O error occurred while reading or writing a tensor.
+    Io(String),
+}
+
+impl fmt::Display for TensorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IncompatibleShapes(lhs, rhs) => {
+                write!(f, "incompatible shapes: {lhs:?} and {rhs:?}")
+            }
+            Self::InvalidEinsumSpec(spec) => write!(f, "invalid einsum spec: {spec}"),
+            Self::SingularMatrix => write!(f, "matrix is singular and cannot be inverted"),
+            Self::InvalidNpyFile(msg) => write!(f, "invalid .npy file: {msg}"),
+            Self::Io(msg) => write!(f, "I/O error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for TensorError {}
@@ -0,0 +1,404 @@
+//! Shape, strides, and offset bookkeeping for [`Tensor`](super::Tensor), kept separate from the
+//! data it indexes into so that ops like `transpose`/`permute`/`narrow`/`broadcast` can produce a
+//! new view without touching the underlying buffer.
+
+use super::error::TensorError;
+
+/// Describes how a flat, row-major `Vec<T>` should be interpreted as an N-dimension array: its
+/// `shape`, the `strides` used to turn a multi-dimension index into a flat position, and an
+/// `offset` into the data marking where this view starts (nonzero after [`Layout::narrow`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    shape: Vec<usize>,
+    strides: Vec<usize>,
+    offset: usize,
+}
+
+impl From<&[usize]> for Layout {
+    fn from(shape: &[usize]) -> Self {
+        Self {
+            shape: shape.to_vec(),
+            strides: contiguous_strides(shape),
+            offset: 0,
+        }
+    }
+}
+
+impl<const N: usize> From<&[usize; N]> for Layout {
+    fn from(shape: &[usize; N]) -> Self {
+        Layout::from(shape.as_slice())
+    }
+}
+
+impl From<&Vec<usize>> for Layout {
+    fn from(shape: &Vec<usize>) -> Self {
+        Layout::from(shape.as_slice())
+    }
+}
+
+impl Layout {
+    /// The layout of a single scalar: rank 0, one element.
+    pub fn scalar() -> Self {
+        Self {
+            shape: Vec::new(),
+            strides: Vec::new(),
+            offset: 0,
+        }
+    }
+
+    /// The shape of the tensor this layout describes.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// The strides used by [`Layout::index_to_position`].
+    pub fn strides(&self) -> &[usize] {
+        &self.strides
+    }
+
+    /// The total number of elements, i.e. the product of `shape`.
+    pub fn elems(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    /// Converts a multi-dimension index into a flat position into the underlying data.
+    pub fn index_to_position(&self, index: &[usize]) -> usize {
+        self.offset
+            + index
+                .iter()
+                .zip(self.strides.iter())
+                .map(|(i, s)| i * s)
+                .sum::<usize>()
+    }
+
+    /// Converts a flat position back into a multi-dimension index, unraveled in row-major order
+    /// over `shape`. `strides` and `offset` are ignored, so this is the inverse of
+    /// [`Layout::index_to_position`] only for a contiguous layout with a zero offset.
+    pub fn position_to_index(&self, mut pos: usize) -> Vec<usize> {
+        let mut index = vec![0; self.shape.len()];
+        for i in 0..self.shape.len() {
+            let stride: usize = self.shape[i + 1..].iter().product();
+            index[i] = if stride == 0 { 0 } else { pos / stride };
+            if stride != 0 {
+                pos %= stride;
+            }
+        }
+        index
+    }
+
+    /// Iterates over every valid multi-dimension index of this layout, in row-major order.
+    pub fn iter_index(&self) -> IndexIterator<'_> {
+        IndexIterator::new(&self.shape)
+    }
+
+    /// Iterates over every valid flat position of this layout, in row-major order.
+    pub fn iter_position(&self) -> PositionIterator<'_> {
+        PositionIterator {
+            layout: self,
+            indices: self.iter_index(),
+        }
+    }
+
+    /// Removes all singleton dimensions.
+    pub fn squeeze(&self) -> Self {
+        let mut shape = Vec::new();
+        let mut strides = Vec::new();
+        for (&dim, &stride) in self.shape.iter().zip(self.strides.iter()) {
+            if dim != 1 {
+                shape.push(dim);
+                strides.push(stride);
+            }
+        }
+        Self {
+            shape,
+            strides,
+            offset: self.offset,
+        }
+    }
+
+    /// Swaps two dimensions without moving any data.
+    pub fn transpose(&self, dim0: usize, dim1: usize) -> Result<Self, TensorError> {
+        if dim0 >= self.shape.len() || dim1 >= self.shape.len() {
+            return Err(TensorError::IncompatibleShapes(
+                self.shape.clone(),
+                vec![dim0, dim1],
+            ));
+        }
+        let mut shape = self.shape.clone();
+        let mut strides = self.strides.clone();
+        shape.swap(dim0, dim1);
+        strides.swap(dim0, dim1);
+        Ok(Self {
+            shape,
+            strides,
+            offset: self.offset,
+        })
+    }
+
+    /// Reorders the dimensions according to `permutation` without moving any data.
+    pub fn permute(&self, permutation: &[usize]) -> Result<Self, TensorError> {
+        let rank = self.shape.len();
+        if permutation.len() != rank {
+            return Err(TensorError::IncompatibleShapes(
+                self.shape.clone(),
+                permutation.to_vec(),
+            ));
+        }
+        let mut seen = vec![false; rank];
+        for &p in permutation {
+            if p >= rank || seen[p] {
+                return Err(TensorError::IncompatibleShapes(
+                    self.shape.clone(),
+                    permutation.to_vec(),
+                ));
+            }
+            seen[p] = true;
+        }
+        let shape = permutation.iter().map(|&p| self.shape[p]).collect();
+        let strides = permutation.iter().map(|&p| self.strides[p]).collect();
+        Ok(Self {
+            shape,
+            strides,
+            offset: self.offset,
+        })
+    }
+
+    /// Reshapes to `shape` if the current layout is contiguous (so the data can be reinterpreted
+    /// in place), returning `None` otherwise so the caller can fall back to materializing the
+    /// data first.
+    pub fn reshape(&self, shape: &[usize]) -> Result<Option<Self>, TensorError> {
+        let elems: usize = shape.iter().product();
+        if elems != self.elems() {
+            return Err(TensorError::IncompatibleShapes(
+                self.shape.clone(),
+                shape.to_vec(),
+            ));
+        }
+        if self.strides == contiguous_strides(&self.shape) {
+            Ok(Some(Self {
+                shape: shape.to_vec(),
+                strides: contiguous_strides(shape),
+                offset: self.offset,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Takes a contiguous range of length `len` along `dim`, starting at `start`. Implemented by
+    /// shrinking `shape[dim]` to `len` and advancing `offset` by `start * strides[dim]`, so the
+    /// result shares the same underlying data as `self`.
+    pub fn narrow(&self, dim: usize, start: usize, len: usize) -> Result<Self, TensorError> {
+        if dim >= self.shape.len() {
+            return Err(TensorError::IncompatibleShapes(
+                self.shape.clone(),
+                vec![dim],
+            ));
+        }
+        if start
+            .checked_add(len)
+            .map_or(true, |end| end > self.shape[dim])
+        {
+            return Err(TensorError::IncompatibleShapes(
+                self.shape.clone(),
+                vec![start, len],
+            ));
+        }
+        let mut shape = self.shape.clone();
+        shape[dim] = len;
+        Ok(Self {
+            shape,
+            strides: self.strides.clone(),
+            offset: self.offset + start * self.strides[dim],
+        })
+    }
+
+    /// Expands `self` to `shape` following [NumPy's broadcasting rules], but one-sided: `shape`
+    /// must have at least as many dimensions as `self`, and every dimension of `self` must either
+    /// already match or be size 1 (given stride 0 to expand it). This is `self`'s side of what
+    /// [`Layout::broadcast`] computes for two operands at once.
+    ///
+    /// [NumPy's broadcasting rules]: https://numpy.org/doc/stable/user/basics.broadcasting.html
+    pub fn expand(&self, shape: &[usize]) -> Result<Self, TensorError> {
+        if shape.len() < self.shape.len() {
+            return Err(TensorError::IncompatibleShapes(
+                self.shape.clone(),
+                shape.to_vec(),
+            ));
+        }
+        let padded = self.pad_left(shape.len());
+        let mut strides = vec![0; shape.len()];
+        for i in 0..shape.len() {
+            strides[i] = match (padded.shape[i], shape[i]) {
+                (l, r) if l == r => padded.strides[i],
+                (1, _) => 0,
+                _ => {
+                    return Err(TensorError::IncompatibleShapes(
+                        self.shape.clone(),
+                        shape.to_vec(),
+                    ))
+                }
+            };
+        }
+        Ok(Self {
+            shape: shape.to_vec(),
+            strides,
+            offset: self.offset,
+        })
+    }
+
+    /// Computes the keepdims layout produced by collapsing `dims` to size 1 (used by
+    /// [`super::Tensor::reduce`] for `sum`/`product`), alongside a same-rank "reducer" layout
+    /// whose strides are zeroed out along `dims`. Indexing the reducer with any of `self`'s
+    /// indices yields the flat position of the corresponding entry in the reduced result, which
+    /// lets a single pass over `self`'s indices accumulate directly into the output buffer.
+    pub fn reduce(&self, dims: &[usize]) -> Result<(Self, Self), TensorError> {
+        for &dim in dims {
+            if dim >= self.shape.len() {
+                return Err(TensorError::IncompatibleShapes(
+                    self.shape.clone(),
+                    dims.to_vec(),
+                ));
+            }
+        }
+        let mut shape = self.shape.clone();
+        for &dim in dims {
+            shape[dim] = 1;
+        }
+        let layout = Self::from(shape.as_slice());
+        let mut reducer = layout.clone();
+        for &dim in dims {
+            reducer.strides[dim] = 0;
+        }
+        Ok((layout, reducer))
+    }
+
+    /// Broadcasts `self` and `other` together following [NumPy's broadcasting rules]: shapes are
+    /// aligned from the right, padded with 1s on the left, and any dimension of size 1 is expanded
+    /// (given stride 0) to match the other operand's size at that dimension.
+    ///
+    /// [NumPy's broadcasting rules]: https://numpy.org/doc/stable/user/basics.broadcasting.html
+    pub fn broadcast(&self, other: &Self) -> Result<(Self, Self), TensorError> {
+        let rank = self.shape.len().max(other.shape.len());
+        let lhs = self.pad_left(rank);
+        let rhs = other.pad_left(rank);
+
+        let mut lhs_shape = vec![0; rank];
+        let mut lhs_strides = vec![0; rank];
+        let mut rhs_shape = vec![0; rank];
+        let mut rhs_strides = vec![0; rank];
+        for i in 0..rank {
+            let dim = match (lhs.shape[i], rhs.shape[i]) {
+                (l, r) if l == r => l,
+                (1, r) => r,
+                (l, 1) => l,
+                _ => {
+                    return Err(TensorError::IncompatibleShapes(
+                        self.shape.clone(),
+                        other.shape.clone(),
+                    ))
+                }
+            };
+            lhs_shape[i] = dim;
+            rhs_shape[i] = dim;
+            lhs_strides[i] = if lhs.shape[i] == dim { lhs.strides[i] } else { 0 };
+            rhs_strides[i] = if rhs.shape[i] == dim { rhs.strides[i] } else { 0 };
+        }
+
+        Ok((
+            Self {
+                shape: lhs_shape,
+                strides: lhs_strides,
+                offset: self.offset,
+            },
+            Self {
+                shape: rhs_shape,
+                strides: rhs_strides,
+                offset: other.offset,
+            },
+        ))
+    }
+
+    /// Pads this layout on the left with size-1, stride-0 dimensions until it has `rank`
+    /// dimensions, the first step of aligning two shapes for [`Layout::broadcast`].
+    fn pad_left(&self, rank: usize) -> Self {
+        let pad = rank - self.shape.len();
+        let mut shape = vec![1; pad];
+        let mut strides = vec![0; pad];
+        shape.extend_from_slice(&self.shape);
+        strides.extend_from_slice(&self.strides);
+        Self {
+            shape,
+            strides,
+            offset: self.offset,
+        }
+    }
+}
+
+/// Returns the strides of a contiguous, row-major layout of `shape`.
+fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![0; shape.len()];
+    let mut acc = 1;
+    for i in (0..shape.len()).rev() {
+        strides[i] = acc;
+        acc *= shape[i];
+    }
+    strides
+}
+
+/// Iterates over every multi-dimension index of a shape, in row-major order.
+pub struct IndexIterator<'a> {
+    shape: &'a [usize],
+    next: Option<Vec<usize>>,
+}
+
+impl<'a> IndexIterator<'a> {
+    fn new(shape: &'a [usize]) -> Self {
+        let next = if shape.iter().any(|&dim| dim == 0) {
+            None
+        } else {
+            Some(vec![0; shape.len()])
+        };
+        Self { shape, next }
+    }
+}
+
+impl<'a> Iterator for IndexIterator<'a> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.next.take()?;
+        self.next = increment(&index, self.shape);
+        Some(index)
+    }
+}
+
+/// Increments a row-major multi-dimension index, carrying over into more significant dimensions,
+/// or returns `None` once every index has been visited.
+fn increment(index: &[usize], shape: &[usize]) -> Option<Vec<usize>> {
+    let mut next = index.to_vec();
+    for i in (0..shape.len()).rev() {
+        next[i] += 1;
+        if next[i] < shape[i] {
+            return Some(next);
+        }
+        next[i] = 0;
+    }
+    None
+}
+
+/// A row-major iterator over the flat positions of a [`Layout`].
+pub struct PositionIterator<'a> {
+    layout: &'a Layout,
+    indices: IndexIterator<'a>,
+}
+
+impl<'a> Iterator for PositionIterator<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.indices
+            .next()
+            .map(|index| self.layout.index_to_position(&index))
+    }
+}
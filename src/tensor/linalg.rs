@@ -0,0 +1,118 @@
+//! Square-matrix linear algebra for [`Tensor`]: determinant, minors, and inversion.
+
+use num_traits::Float;
+
+use super::error::TensorError;
+use super::Tensor;
+
+impl<T: Float> Tensor<T> {
+    /// The `(n-1)×(n-1)` submatrix of a square matrix with `row` and `col` removed.
+    pub fn minor(&self, row: usize, col: usize) -> Result<Tensor<T>, TensorError> {
+        let n = self.square_dim()?;
+        if row >= n || col >= n {
+            return Err(TensorError::IncompatibleShapes(vec![n, n], vec![row, col]));
+        }
+        let rows: Vec<usize> = (0..n).filter(|&r| r != row).collect();
+        let cols: Vec<usize> = (0..n).filter(|&c| c != col).collect();
+        self.select(0, &rows)?.select(1, &cols)
+    }
+
+    /// The determinant of a square matrix, computed via LU decomposition with partial pivoting
+    /// for numerical stability: the determinant is the signed product of the pivots, where the
+    /// sign flips with every row swap.
+    pub fn det(&self) -> Result<T, TensorError> {
+        let n = self.square_dim()?;
+        let (lu, _, sign) = match self.lu_decompose(n) {
+            Ok(factorization) => factorization,
+            // A near-zero pivot means the matrix is singular, so its determinant is zero.
+            Err(TensorError::SingularMatrix) => return Ok(T::zero()),
+            Err(e) => return Err(e),
+        };
+        let mut det = sign;
+        for i in 0..n {
+            det = det * lu[i][i];
+        }
+        Ok(det)
+    }
+
+    /// The inverse of a square matrix, solved column-by-column against the identity matrix using
+    /// the same LU factorization as [`Tensor::det`]. Returns [`TensorError::SingularMatrix`] when
+    /// the matrix has no inverse.
+    pub fn inverse(&self) -> Result<Tensor<T>, TensorError> {
+        let n = self.square_dim()?;
+        let (lu, perm, _) = self.lu_decompose(n)?;
+
+        let mut inv = vec![T::zero(); n * n];
+        for col in 0..n {
+            // Forward substitution solves L y = P·e_col (L has an implicit unit diagonal, and `P`
+            // is the row permutation from partial pivoting), then back substitution solves
+            // U x = y.
+            let mut y = vec![T::zero(); n];
+            for i in 0..n {
+                let e = if perm[i] == col { T::one() } else { T::zero() };
+                let mut sum = e;
+                for k in 0..i {
+                    sum = sum - lu[i][k] * y[k];
+                }
+                y[i] = sum;
+            }
+            let mut x = vec![T::zero(); n];
+            for i in (0..n).rev() {
+                let mut sum = y[i];
+                for k in (i + 1)..n {
+                    sum = sum - lu[i][k] * x[k];
+                }
+                x[i] = sum / lu[i][i];
+            }
+            for (row, &xi) in x.iter().enumerate() {
+                inv[row * n + col] = xi;
+            }
+        }
+        Tensor::shaped(&[n, n], &inv)
+    }
+
+    fn square_dim(&self) -> Result<usize, TensorError> {
+        let shape = self.layout().shape();
+        if shape.len() != 2 || shape[0] != shape[1] {
+            return Err(TensorError::IncompatibleShapes(
+                shape.to_vec(),
+                shape.to_vec(),
+            ));
+        }
+        Ok(shape[0])
+    }
+
+    /// LU-decomposes a square matrix with partial pivoting. The result packs `L` (unit diagonal,
+    /// implicit) below the diagonal and `U` on and above it into a single `n×n` matrix, alongside
+    /// the row permutation applied (`perm[i]` is the original row now at row `i`) and the sign of
+    /// that permutation, used by [`Tensor::det`].
+    fn lu_decompose(&self, n: usize) -> Result<(Vec<Vec<T>>, Vec<usize>, T), TensorError> {
+        let mut a: Vec<Vec<T>> = (0..n)
+            .map(|r| (0..n).map(|c| self[&[r, c]]).collect())
+            .collect();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut sign = T::one();
+
+        for k in 0..n {
+            let pivot_row = (k..n)
+                .max_by(|&r1, &r2| a[r1][k].abs().partial_cmp(&a[r2][k].abs()).unwrap())
+                .expect("k..n is non-empty");
+            if a[pivot_row][k].abs() <= T::epsilon() {
+                return Err(TensorError::SingularMatrix);
+            }
+            if pivot_row != k {
+                a.swap(pivot_row, k);
+                perm.swap(pivot_row, k);
+                sign = -sign;
+            }
+            for i in (k + 1)..n {
+                let factor = a[i][k] / a[k][k];
+                a[i][k] = factor;
+                for j in (k + 1)..n {
+                    a[i][j] = a[i][j] - factor * a[k][j];
+                }
+            }
+        }
+        Ok((a, perm, sign))
+    }
+}
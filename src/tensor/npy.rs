@@ -0,0 +1,202 @@
+//! `.npy`/`.npz` serialization for [`Tensor`], interoperating with the NumPy ecosystem.
+
+use std::io::{self, Read, Seek, Write};
+
+use num_traits::Float;
+
+use super::error::TensorError;
+use super::Tensor;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Element types that can be written to / read from a `.npy` file. Implemented for the
+/// floating-point types [`Tensor`] supports; other `Float` impls would need their own `DESCR` and
+/// byte layout before they could round-trip through NumPy.
+pub trait NpyElement: Float {
+    /// The NumPy `descr` string for this type, e.g. `"<f4"`.
+    const DESCR: &'static str;
+
+    fn to_le_bytes(self) -> Vec<u8>;
+    fn from_le_bytes(bytes: &[u8]) -> Self;
+}
+
+impl NpyElement for f32 {
+    const DESCR: &'static str = "<f4";
+
+    fn to_le_bytes(self) -> Vec<u8> {
+        f32::to_le_bytes(self).to_vec()
+    }
+
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        f32::from_le_bytes(bytes.try_into().expect("4 bytes for an f32"))
+    }
+}
+
+impl NpyElement for f64 {
+    const DESCR: &'static str = "<f8";
+
+    fn to_le_bytes(self) -> Vec<u8> {
+        f64::to_le_bytes(self).to_vec()
+    }
+
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        f64::from_le_bytes(bytes.try_into().expect("8 bytes for an f64"))
+    }
+}
+
+impl<T: NpyElement> Tensor<T> {
+    /// Writes this tensor as a `.npy` file to `w`. Non-contiguous tensors (produced by
+    /// `transpose`/`permute`/`narrow`/`expand`) are materialized to row-major order first, by
+    /// reading through `self`'s own logical iteration order (which already accounts for strides
+    /// and offset), so the written buffer matches the declared `shape`.
+    pub fn save_npy<W: Write>(&self, w: &mut W) -> Result<(), TensorError> {
+        let shape = self.layout().shape();
+        let contiguous: Vec<T> = self.into_iter().copied().collect();
+
+        let mut header = format!(
+            "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+            T::DESCR,
+            python_tuple(shape),
+        );
+        // Pad so that magic (6) + version (2) + header length (2) + header + '\n' lands on a
+        // 64-byte boundary, as the NumPy format requires.
+        let unpadded_len = MAGIC.len() + 2 + 2 + header.len() + 1;
+        let padding = (64 - unpadded_len % 64) % 64;
+        header.extend(std::iter::repeat(' ').take(padding));
+        header.push('\n');
+
+        w.write_all(MAGIC).map_err(io_error)?;
+        w.write_all(&[1, 0]).map_err(io_error)?; // version 1.0
+        w.write_all(&(header.len() as u16).to_le_bytes())
+            .map_err(io_error)?;
+        w.write_all(header.as_bytes()).map_err(io_error)?;
+        for x in contiguous {
+            w.write_all(&x.to_le_bytes()).map_err(io_error)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a tensor previously written by [`Tensor::save_npy`].
+    pub fn load_npy<R: Read>(r: &mut R) -> Result<Self, TensorError> {
+        let mut magic = [0u8; 6];
+        r.read_exact(&mut magic).map_err(io_error)?;
+        if magic != MAGIC {
+            return Err(TensorError::InvalidNpyFile("bad magic bytes".to_string()));
+        }
+
+        let mut version = [0u8; 2];
+        r.read_exact(&mut version).map_err(io_error)?;
+        let header_len = if version[0] == 1 {
+            let mut len_bytes = [0u8; 2];
+            r.read_exact(&mut len_bytes).map_err(io_error)?;
+            u16::from_le_bytes(len_bytes) as usize
+        } else {
+            let mut len_bytes = [0u8; 4];
+            r.read_exact(&mut len_bytes).map_err(io_error)?;
+            u32::from_le_bytes(len_bytes) as usize
+        };
+        let mut header = vec![0u8; header_len];
+        r.read_exact(&mut header).map_err(io_error)?;
+        let header = String::from_utf8(header)
+            .map_err(|_| TensorError::InvalidNpyFile("header is not valid UTF-8".to_string()))?;
+
+        let descr = extract_descr(&header)?;
+        if descr != T::DESCR {
+            return Err(TensorError::InvalidNpyFile(format!(
+                "expected dtype {}, got {descr}",
+                T::DESCR
+            )));
+        }
+        let shape = extract_shape(&header)?;
+
+        let elem_size = std::mem::size_of::<T>();
+        let mut data = vec![0u8; shape.iter().product::<usize>() * elem_size];
+        r.read_exact(&mut data).map_err(io_error)?;
+        let data: Vec<T> = data.chunks_exact(elem_size).map(T::from_le_bytes).collect();
+        Tensor::shaped(&shape, &data)
+    }
+}
+
+/// Writes several named tensors to a `.npz` archive (a zip of `.npy` files named `"{name}.npy"`).
+pub fn save_npz<W: Write + Seek, T: NpyElement>(
+    w: W,
+    arrays: &[(&str, &Tensor<T>)],
+) -> Result<(), TensorError> {
+    let mut zip = zip::ZipWriter::new(w);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    for (name, tensor) in arrays {
+        zip.start_file(format!("{name}.npy"), options)
+            .map_err(zip_error)?;
+        tensor.save_npy(&mut zip)?;
+    }
+    zip.finish().map_err(zip_error)?;
+    Ok(())
+}
+
+/// Reads every array out of a `.npz` archive, keyed by name (with the `.npy` suffix stripped).
+pub fn load_npz<R: Read + Seek, T: NpyElement>(r: R) -> Result<Vec<(String, Tensor<T>)>, TensorError> {
+    let mut archive = zip::ZipArchive::new(r).map_err(zip_error)?;
+    let mut out = Vec::with_capacity(archive.len());
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(zip_error)?;
+        let name = file.name().trim_end_matches(".npy").to_string();
+        out.push((name, Tensor::load_npy(&mut file)?));
+    }
+    Ok(out)
+}
+
+fn python_tuple(shape: &[usize]) -> String {
+    if shape.len() == 1 {
+        format!("({},)", shape[0])
+    } else {
+        format!(
+            "({})",
+            shape
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+fn extract_descr(header: &str) -> Result<&str, TensorError> {
+    let key = "'descr': '";
+    let start = header
+        .find(key)
+        .ok_or_else(|| TensorError::InvalidNpyFile("missing descr field".to_string()))?
+        + key.len();
+    let end = header[start..]
+        .find('\'')
+        .ok_or_else(|| TensorError::InvalidNpyFile("unterminated descr field".to_string()))?;
+    Ok(&header[start..start + end])
+}
+
+fn extract_shape(header: &str) -> Result<Vec<usize>, TensorError> {
+    let key = "'shape': (";
+    let start = header
+        .find(key)
+        .ok_or_else(|| TensorError::InvalidNpyFile("missing shape field".to_string()))?
+        + key.len();
+    let end = header[start..]
+        .find(')')
+        .ok_or_else(|| TensorError::InvalidNpyFile("unterminated shape field".to_string()))?;
+    header[start..start + end]
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<usize>()
+                .map_err(|_| TensorError::InvalidNpyFile(format!("invalid shape entry {s:?}")))
+        })
+        .collect()
+}
+
+fn io_error(e: io::Error) -> TensorError {
+    TensorError::Io(e.to_string())
+}
+
+fn zip_error(e: zip::result::ZipError) -> TensorError {
+    TensorError::Io(e.to_string())
+}
@@ -0,0 +1,23 @@
+use candle::tensor::Tensor;
+
+#[test]
+fn test_allclose_true_within_tolerance() {
+    let a = Tensor::shaped(&[3], &[1.0_f32, 2.0, 3.0]).unwrap();
+    let b = Tensor::shaped(&[3], &[1.00001_f32, 2.00001, 3.00001]).unwrap();
+    assert!(a.allclose_default(&b).unwrap());
+}
+
+#[test]
+fn test_allclose_false_outside_tolerance() {
+    let a = Tensor::shaped(&[3], &[1.0_f32, 2.0, 3.0]).unwrap();
+    let b = Tensor::shaped(&[3], &[1.0_f32, 2.0, 3.5]).unwrap();
+    assert!(!a.allclose_default(&b).unwrap());
+}
+
+#[test]
+fn test_is_close_mask() {
+    let a = Tensor::shaped(&[2], &[1.0_f32, 2.0]).unwrap();
+    let b = Tensor::shaped(&[2], &[1.0_f32, 3.0]).unwrap();
+    let mask: Vec<f32> = a.is_close(&b).unwrap().into_iter().copied().collect();
+    assert_eq!(mask, vec![1.0, 0.0]);
+}
@@ -0,0 +1,69 @@
+use candle::tensor::autograd::Var;
+use candle::tensor::Tensor;
+
+#[test]
+fn test_backward_add_mul() {
+    // f(x, y) = (x + y) * x, at x=3, y=4 -> f=21, df/dx=2x+y=10, df/dy=x=3
+    let x = Var::new(Tensor::scalar(3.0_f32));
+    let y = Var::new(Tensor::scalar(4.0_f32));
+    let sum = x.add(&y).unwrap();
+    let out = sum.mul(&x).unwrap();
+    out.backward().unwrap();
+
+    assert_eq!(*out.value().into_iter().next().unwrap(), 21.0);
+    assert_eq!(*x.grad().unwrap().into_iter().next().unwrap(), 10.0);
+    assert_eq!(*y.grad().unwrap().into_iter().next().unwrap(), 3.0);
+}
+
+#[test]
+fn test_backward_broadcast_add() {
+    let x = Var::new(Tensor::shaped(&[2, 3], &[1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap());
+    let y = Var::new(Tensor::shaped(&[3], &[1.0_f32, 1.0, 1.0]).unwrap());
+    let z = x.add(&y).unwrap();
+    let loss = z.sum(&[0, 1]).unwrap();
+    loss.backward().unwrap();
+
+    let dx: Vec<f32> = x.grad().unwrap().into_iter().copied().collect();
+    assert_eq!(dx, vec![1.0; 6]);
+
+    // y is broadcast across the 2 rows, so its gradient sums both rows' contributions.
+    let dy: Vec<f32> = y.grad().unwrap().into_iter().copied().collect();
+    assert_eq!(dy, vec![2.0, 2.0, 2.0]);
+}
+
+#[test]
+fn test_backward_matmul() {
+    let a = Var::new(Tensor::shaped(&[2, 2], &[1.0_f32, 2.0, 3.0, 4.0]).unwrap());
+    let b = Var::new(Tensor::shaped(&[2, 2], &[5.0_f32, 6.0, 7.0, 8.0]).unwrap());
+    let c = a.matmul(&b).unwrap();
+    let loss = c.sum(&[0, 1]).unwrap();
+    loss.backward().unwrap();
+
+    let da: Vec<f32> = a.grad().unwrap().into_iter().copied().collect();
+    let db: Vec<f32> = b.grad().unwrap().into_iter().copied().collect();
+    assert_eq!(da, vec![11.0, 15.0, 11.0, 15.0]);
+    assert_eq!(db, vec![4.0, 4.0, 6.0, 6.0]);
+}
+
+#[test]
+fn test_backward_matmul_broadcast_batch() {
+    // a has batch size 1, b has batch size 2: matmul broadcasts the batch dimension, so the
+    // backward pass must un-broadcast each gradient back down to its own parent's batch size.
+    let a = Var::new(Tensor::shaped(&[1, 2, 2], &[1.0_f32, 0.0, 0.0, 1.0]).unwrap());
+    let b = Var::new(
+        Tensor::shaped(&[2, 2, 2], &[1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]).unwrap(),
+    );
+    let c = a.matmul(&b).unwrap();
+    let loss = c.sum(&[0, 1, 2]).unwrap();
+    loss.backward().unwrap();
+
+    let da = a.grad().unwrap();
+    assert_eq!(da.layout().shape(), &[1, 2, 2]);
+    let da: Vec<f32> = da.into_iter().copied().collect();
+    assert_eq!(da, vec![14.0, 22.0, 14.0, 22.0]);
+
+    let db = b.grad().unwrap();
+    assert_eq!(db.layout().shape(), &[2, 2, 2]);
+    let db: Vec<f32> = db.into_iter().copied().collect();
+    assert_eq!(db, vec![1.0; 8]);
+}
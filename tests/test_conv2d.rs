@@ -0,0 +1,52 @@
+use candle::tensor::Tensor;
+
+#[test]
+fn test_conv2d_no_padding() {
+    // 1x1x3x3 input, 1x1x2x2 kernel picking out the diagonal of each window.
+    let input = Tensor::shaped(
+        &[1, 1, 3, 3],
+        &[1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0],
+    )
+    .unwrap();
+    let kernel = Tensor::shaped(&[1, 1, 2, 2], &[1.0_f32, 0.0, 0.0, 1.0]).unwrap();
+    let out = input.conv2d(&kernel, (1, 1), (0, 0)).unwrap();
+
+    assert_eq!(out.layout().shape(), &[1, 1, 2, 2]);
+    let data: Vec<f32> = out.into_iter().copied().collect();
+    assert_eq!(data, vec![6.0, 8.0, 12.0, 14.0]);
+}
+
+#[test]
+fn test_conv2d_with_padding() {
+    // A 1x1 kernel scaling by 2: zero padding means the border of the output is 0, and the
+    // interior exactly matches the scaled input.
+    let input = Tensor::shaped(&[1, 1, 2, 2], &[1.0_f32, 2.0, 3.0, 4.0]).unwrap();
+    let kernel = Tensor::shaped(&[1, 1, 1, 1], &[2.0_f32]).unwrap();
+    let out = input.conv2d(&kernel, (1, 1), (1, 1)).unwrap();
+
+    assert_eq!(out.layout().shape(), &[1, 1, 4, 4]);
+    let data: Vec<f32> = out.into_iter().copied().collect();
+    assert_eq!(
+        data,
+        vec![
+            0.0, 0.0, 0.0, 0.0, //
+            0.0, 2.0, 4.0, 0.0, //
+            0.0, 6.0, 8.0, 0.0, //
+            0.0, 0.0, 0.0, 0.0,
+        ]
+    );
+}
+
+#[test]
+fn test_conv2d_zero_stride_errors() {
+    let input = Tensor::shaped(&[1, 1, 3, 3], &[0.0_f32; 9]).unwrap();
+    let kernel = Tensor::shaped(&[1, 1, 2, 2], &[0.0_f32; 4]).unwrap();
+    assert!(input.conv2d(&kernel, (0, 1), (0, 0)).is_err());
+}
+
+#[test]
+fn test_conv2d_kernel_larger_than_input_errors() {
+    let input = Tensor::shaped(&[1, 1, 2, 2], &[0.0_f32; 4]).unwrap();
+    let kernel = Tensor::shaped(&[1, 1, 3, 3], &[0.0_f32; 9]).unwrap();
+    assert!(input.conv2d(&kernel, (1, 1), (0, 0)).is_err());
+}
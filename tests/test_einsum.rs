@@ -0,0 +1,39 @@
+use candle::tensor::Tensor;
+
+#[test]
+fn test_einsum_matmul() {
+    let a = Tensor::shaped(&[2, 3], &[1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let b = Tensor::shaped(&[3, 2], &[7.0_f32, 8.0, 9.0, 10.0, 11.0, 12.0]).unwrap();
+    let c = Tensor::einsum("ij,jk->ik", &[&a, &b]).unwrap();
+    let expected = a.matmul(&b).unwrap();
+    assert_eq!(c.layout().shape(), expected.layout().shape());
+
+    let c_data: Vec<f32> = c.into_iter().copied().collect();
+    let expected_data: Vec<f32> = expected.into_iter().copied().collect();
+    assert_eq!(c_data, expected_data);
+}
+
+#[test]
+fn test_einsum_diagonal() {
+    let a =
+        Tensor::shaped(&[3, 3], &[1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]).unwrap();
+    let diag = Tensor::einsum("ii->i", &[&a]).unwrap();
+    let data: Vec<f32> = diag.into_iter().copied().collect();
+    assert_eq!(data, vec![1.0, 5.0, 9.0]);
+}
+
+#[test]
+fn test_einsum_transpose() {
+    let a = Tensor::shaped(&[2, 3], &[1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let t = Tensor::einsum("ij->ji", &[&a]).unwrap();
+    assert_eq!(t.layout().shape(), &[3, 2]);
+    let data: Vec<f32> = t.into_iter().copied().collect();
+    assert_eq!(data, vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+}
+
+#[test]
+fn test_einsum_invalid_output_label_errors() {
+    let a = Tensor::shaped(&[2, 2], &[1.0_f32, 2.0, 3.0, 4.0]).unwrap();
+    let result = Tensor::einsum("ij->ik", &[&a]);
+    assert!(result.is_err());
+}
@@ -0,0 +1,18 @@
+use candle::tensor::Tensor;
+
+#[test]
+fn test_tensor_f64_elementwise_add() {
+    let a = Tensor::shaped(&[2, 2], &[1.0_f64, 2.0, 3.0, 4.0]).unwrap();
+    let b = Tensor::shaped(&[2, 2], &[5.0_f64, 6.0, 7.0, 8.0]).unwrap();
+    let c = (&a + &b).unwrap();
+    let data: Vec<f64> = c.into_iter().copied().collect();
+    assert_eq!(data, vec![6.0, 8.0, 10.0, 12.0]);
+}
+
+#[test]
+fn test_tensor_f32_elementwise_mul() {
+    let a = Tensor::scalar(2.0_f32);
+    let b = Tensor::scalar(3.0_f32);
+    let c = (&a * &b).unwrap();
+    assert_eq!(*c.into_iter().next().unwrap(), 6.0);
+}
@@ -0,0 +1,47 @@
+use candle::tensor::Tensor;
+
+#[test]
+fn test_det_2x2() {
+    let m = Tensor::shaped(&[2, 2], &[4.0_f32, 7.0, 2.0, 6.0]).unwrap();
+    let det = m.det().unwrap();
+    assert!((det - 10.0).abs() < 1e-4);
+}
+
+#[test]
+fn test_det_3x3() {
+    let m = Tensor::shaped(
+        &[3, 3],
+        &[1.0_f32, 2.0, 3.0, 0.0, 1.0, 4.0, 5.0, 6.0, 0.0],
+    )
+    .unwrap();
+    let det = m.det().unwrap();
+    assert!((det - 1.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_minor() {
+    let m = Tensor::shaped(
+        &[3, 3],
+        &[1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0],
+    )
+    .unwrap();
+    let minor = m.minor(1, 1).unwrap();
+    assert_eq!(minor.layout().shape(), &[2, 2]);
+    let data: Vec<f32> = minor.into_iter().copied().collect();
+    assert_eq!(data, vec![1.0, 3.0, 7.0, 9.0]);
+}
+
+#[test]
+fn test_inverse_roundtrip() {
+    let m = Tensor::shaped(&[2, 2], &[4.0_f32, 7.0, 2.0, 6.0]).unwrap();
+    let inv = m.inverse().unwrap();
+    let identity = m.matmul(&inv).unwrap();
+    let expected = Tensor::shaped(&[2, 2], &[1.0_f32, 0.0, 0.0, 1.0]).unwrap();
+    assert!(identity.allclose_default(&expected).unwrap());
+}
+
+#[test]
+fn test_singular_matrix_errors() {
+    let m = Tensor::shaped(&[2, 2], &[1.0_f32, 2.0, 2.0, 4.0]).unwrap();
+    assert!(m.inverse().is_err());
+}
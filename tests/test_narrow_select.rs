@@ -0,0 +1,44 @@
+use candle::tensor::Tensor;
+
+#[test]
+fn test_narrow_view() {
+    let t = Tensor::shaped(&[4], &[1.0_f32, 2.0, 3.0, 4.0]).unwrap();
+    let n = t.narrow(0, 1, 2).unwrap();
+    assert_eq!(n.layout().shape(), &[2]);
+    let data: Vec<f32> = n.into_iter().copied().collect();
+    assert_eq!(data, vec![2.0, 3.0]);
+}
+
+#[test]
+fn test_narrow_out_of_bounds_errors() {
+    let t = Tensor::shaped(&[4], &[1.0_f32, 2.0, 3.0, 4.0]).unwrap();
+    assert!(t.narrow(0, 3, 2).is_err());
+}
+
+#[test]
+fn test_narrow_is_a_view_into_a_later_row() {
+    let t = Tensor::shaped(
+        &[2, 4],
+        &[1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0],
+    )
+    .unwrap();
+    let row = t.narrow(0, 1, 1).unwrap();
+    let reshaped = row.reshape(&[2, 2]).unwrap();
+    let data: Vec<f32> = reshaped.into_iter().copied().collect();
+    assert_eq!(data, vec![5.0, 6.0, 7.0, 8.0]);
+}
+
+#[test]
+fn test_select_gathers_rows() {
+    let t = Tensor::shaped(&[3, 2], &[1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let s = t.select(0, &[2, 0]).unwrap();
+    assert_eq!(s.layout().shape(), &[2, 2]);
+    let data: Vec<f32> = s.into_iter().copied().collect();
+    assert_eq!(data, vec![5.0, 6.0, 1.0, 2.0]);
+}
+
+#[test]
+fn test_select_out_of_bounds_errors() {
+    let t = Tensor::shaped(&[3, 2], &[1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    assert!(t.select(0, &[5]).is_err());
+}
@@ -0,0 +1,47 @@
+use candle::tensor::npy::{load_npz, save_npz};
+use candle::tensor::Tensor;
+
+#[test]
+fn test_npy_round_trip() {
+    let t = Tensor::shaped(&[2, 3], &[1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+
+    let mut buf = Vec::new();
+    t.save_npy(&mut buf).unwrap();
+    let loaded = Tensor::<f32>::load_npy(&mut buf.as_slice()).unwrap();
+
+    assert_eq!(loaded.layout().shape(), t.layout().shape());
+    let data: Vec<f32> = loaded.into_iter().copied().collect();
+    assert_eq!(data, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+}
+
+#[test]
+fn test_npy_round_trip_transposed() {
+    // a = [[1,2,3],[4,5,6]]; transposed its logical order is [1,4,2,5,3,6] under shape [3,2].
+    let a = Tensor::shaped(&[2, 3], &[1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let t = a.transpose(0, 1).unwrap();
+
+    let mut buf = Vec::new();
+    t.save_npy(&mut buf).unwrap();
+    let loaded = Tensor::<f32>::load_npy(&mut buf.as_slice()).unwrap();
+
+    assert_eq!(loaded.layout().shape(), &[3, 2]);
+    let data: Vec<f32> = loaded.into_iter().copied().collect();
+    assert_eq!(data, vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+}
+
+#[test]
+fn test_npz_round_trip() {
+    let a = Tensor::shaped(&[2], &[1.0_f32, 2.0]).unwrap();
+    let b = Tensor::shaped(&[3], &[3.0_f32, 4.0, 5.0]).unwrap();
+
+    let mut buf = std::io::Cursor::new(Vec::new());
+    save_npz(&mut buf, &[("a", &a), ("b", &b)]).unwrap();
+    buf.set_position(0);
+
+    let arrays = load_npz::<_, f32>(buf).unwrap();
+    assert_eq!(arrays.len(), 2);
+    assert!(arrays.iter().any(|(name, t)| name == "a"
+        && t.into_iter().copied().collect::<Vec<f32>>() == vec![1.0, 2.0]));
+    assert!(arrays.iter().any(|(name, t)| name == "b"
+        && t.into_iter().copied().collect::<Vec<f32>>() == vec![3.0, 4.0, 5.0]));
+}